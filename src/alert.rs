@@ -0,0 +1,80 @@
+//! The alarm that fires once a countdown finishes: an optional desktop notification plus an
+//! audible tone (or a user-supplied sound file), falling back to the terminal bell when no audio
+//! device is available.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rodio::source::{SineWave, Source, Zero};
+
+const BEEP_GAP: Duration = Duration::from_millis(200);
+
+pub struct Alert {
+    pub notify: bool,
+    pub title: String,
+    pub message: String,
+    pub sound: Option<PathBuf>,
+    pub beeps: u32,
+    pub freq: f32,
+    pub beep_duration: Duration,
+}
+
+impl Alert {
+    /// Fire the notification (if requested) and the alarm sound, falling back to the terminal
+    /// bell if no audio device could be opened.
+    pub fn fire(&self) {
+        if self.notify {
+            if let Err(error) = notify_rust::Notification::new()
+                .summary(&self.title)
+                .body(&self.message)
+                .show()
+            {
+                eprintln!("act: failed to show desktop notification: {}", error);
+            }
+        }
+
+        if !self.play_sound() {
+            print!("{}", crate::BELL);
+        }
+    }
+
+    /// Play the alarm through the default audio device, repeating `self.beeps` times. Returns
+    /// `false` if no audio device is available, so the caller can fall back to the `BEL` byte.
+    fn play_sound(&self) -> bool {
+        let (_stream, handle) = match rodio::OutputStream::try_default() {
+            Ok(pair) => pair,
+            Err(_) => return false,
+        };
+        let sink = match rodio::Sink::try_new(&handle) {
+            Ok(sink) => sink,
+            Err(_) => return false,
+        };
+
+        for beep in 0..self.beeps {
+            self.append_beep(&sink);
+            if beep + 1 < self.beeps {
+                sink.append(Zero::<f32>::new(1, 44_100).take_duration(BEEP_GAP));
+            }
+        }
+        sink.sleep_until_end();
+        true
+    }
+
+    fn append_beep(&self, sink: &rodio::Sink) {
+        if let Some(path) = &self.sound {
+            if let Ok(file) = File::open(path) {
+                if let Ok(source) = rodio::Decoder::new(BufReader::new(file)) {
+                    sink.append(source);
+                    return;
+                }
+            }
+            eprintln!(
+                "act: couldn't play `{}`, falling back to the default tone",
+                path.display()
+            );
+        }
+        sink.append(SineWave::new(self.freq).take_duration(self.beep_duration));
+    }
+}
@@ -0,0 +1,173 @@
+//! A small bitmap font used to render the countdown text as oversized block glyphs, so `act`
+//! doesn't need to shell out to `figlet` to get a big, legible readout.
+//!
+//! Only the characters that `string_from_duration` can ever produce are covered (the digits
+//! `0`-`9` and the unit letters `d`, `h`, `m`, `s`), plus `:`. Anything else renders as blank
+//! space.
+
+const GLYPH_HEIGHT: usize = 5;
+const GLYPH_WIDTH: usize = 5;
+const FILL: char = '\u{2588}';
+
+fn glyph(character: char) -> [&'static str; GLYPH_HEIGHT] {
+    match character {
+        '0' => [
+            " ### ",
+            "#   #",
+            "#   #",
+            "#   #",
+            " ### ",
+        ],
+        '1' => [
+            "  #  ",
+            " ##  ",
+            "  #  ",
+            "  #  ",
+            " ### ",
+        ],
+        '2' => [
+            " ### ",
+            "    #",
+            " ### ",
+            "#    ",
+            "#####",
+        ],
+        '3' => [
+            "#### ",
+            "    #",
+            " ### ",
+            "    #",
+            "#### ",
+        ],
+        '4' => [
+            "#  # ",
+            "#  # ",
+            "#####",
+            "   # ",
+            "   # ",
+        ],
+        '5' => [
+            "#####",
+            "#    ",
+            "#### ",
+            "    #",
+            "#### ",
+        ],
+        '6' => [
+            " ### ",
+            "#    ",
+            "#### ",
+            "#   #",
+            " ### ",
+        ],
+        '7' => [
+            "#####",
+            "   # ",
+            "  #  ",
+            " #   ",
+            " #   ",
+        ],
+        '8' => [
+            " ### ",
+            "#   #",
+            " ### ",
+            "#   #",
+            " ### ",
+        ],
+        '9' => [
+            " ### ",
+            "#   #",
+            " ####",
+            "    #",
+            " ### ",
+        ],
+        ':' => [
+            "     ",
+            "  #  ",
+            "     ",
+            "  #  ",
+            "     ",
+        ],
+        'd' => [
+            "   # ",
+            "   # ",
+            " ### ",
+            "#  # ",
+            " ####",
+        ],
+        'h' => [
+            "#    ",
+            "#    ",
+            "#### ",
+            "#   #",
+            "#   #",
+        ],
+        'm' => [
+            "     ",
+            "     ",
+            "## # ",
+            "# # #",
+            "# # #",
+        ],
+        's' => [
+            "     ",
+            "     ",
+            " ####",
+            " #   ",
+            " ### ",
+        ],
+        _ => [
+            "     ",
+            "     ",
+            "     ",
+            "     ",
+            "     ",
+        ],
+    }
+}
+
+/// Render `text` as a multi-line string of oversized block glyphs, one glyph per character,
+/// each followed by a single column of space (the last glyph included), filled with `FILL`.
+pub fn render(text: &str) -> String {
+    let mut rows = vec![String::new(); GLYPH_HEIGHT];
+    for character in text.chars() {
+        for (row, pattern) in rows.iter_mut().zip(glyph(character).iter()) {
+            for bit in pattern.chars() {
+                row.push(if bit == '#' { FILL } else { ' ' });
+            }
+            row.push(' ');
+        }
+    }
+    rows.join("\n")
+}
+
+#[test]
+fn render_single_digit_is_five_lines_wide_as_the_glyph() {
+    let expected = [
+        " ### ",
+        "#   #",
+        "#   #",
+        "#   #",
+        " ### ",
+    ]
+    .iter()
+    .map(|row| row.replace('#', "\u{2588}") + " ")
+    .collect::<Vec<_>>()
+    .join("\n");
+    assert_eq!(render("0"), expected);
+}
+
+#[test]
+fn render_unknown_character_is_blank() {
+    let rendered = render("?");
+    assert_eq!(rendered.lines().count(), GLYPH_HEIGHT);
+    assert!(rendered.chars().all(|c| c == ' ' || c == '\n'));
+}
+
+#[test]
+fn render_multiple_characters_concatenates_glyphs() {
+    let rendered = render("0:");
+    for line in rendered.lines() {
+        assert_eq!(line.chars().count(), GLYPH_WIDTH * 2 + 2);
+    }
+}
@@ -1,43 +1,91 @@
 #![feature(duration_as_u128)]
 
+use std::fmt;
 use std::io::{stdout, Write};
 use std::thread;
 use std::time::{Duration, Instant};
 use std::process::Command;
 
 use clap::{App, crate_authors, crate_description, crate_name, crate_version};
+use regex::Regex;
 
 use termion::input::TermRead;
 use termion::raw::IntoRawMode;
 use termion::screen::AlternateScreen;
 
-fn duration_from_string(string: &str) -> Duration {
-    let mut numbers = String::new();
-    let mut seconds = 0;
-    for character in string.chars() {
-        match character {
-            's' => {
-                seconds += u64::from_str_radix(&numbers, 10).unwrap();
-                numbers.clear();
-            }
-            'm' => {
-                seconds += u64::from_str_radix(&numbers, 10).unwrap() * 60;
-                numbers.clear();
-            }
-            'h' => {
-                seconds += u64::from_str_radix(&numbers, 10).unwrap() * 60 * 60;
-                numbers.clear();
-            }
-            'd' => {
-                seconds += u64::from_str_radix(&numbers, 10).unwrap() * 60 * 60 * 24;
-                numbers.clear();
-            }
-            char if char.is_digit(10) => numbers.push(char),
-            ' ' => {}
-            char => unimplemented!(),
+mod alert;
+mod bigtext;
+mod pomodoro;
+
+/// An unparseable `DURATION` argument, carrying a message suitable for printing straight to the
+/// user rather than panicking.
+#[derive(Debug)]
+struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn duration_from_string(string: &str) -> Result<Duration, ParseError> {
+    let string = string.trim();
+
+    if let Some(clock) = string.strip_prefix('@') {
+        return duration_until_clock_time(clock);
+    }
+
+    let re = Regex::new(
+        r"^(?:(?P<days>\d+)d ?)?(?:(?P<hours>\d+)h ?)?(?:(?P<minutes>\d+)m(?:in)? ?)?(?:(?P<seconds>\d+)s? ?)?$",
+    )
+    .unwrap();
+
+    let invalid = || ParseError(format!("`{}` isn't a valid duration", string));
+
+    let captures = re.captures(string).ok_or_else(invalid)?;
+    if captures.iter().skip(1).all(|group| group.is_none()) {
+        return Err(invalid());
+    }
+
+    let field = |name| -> Result<u64, ParseError> {
+        match captures.name(name) {
+            Some(m) => m.as_str().parse().map_err(|_| invalid()),
+            None => Ok(0),
         }
+    };
+
+    let days = field("days")?;
+    let hours = field("hours")?;
+    let minutes = field("minutes")?;
+    let seconds = field("seconds")?;
+
+    Ok(Duration::from_secs(
+        seconds + minutes * 60 + hours * 60 * 60 + days * 60 * 60 * 24,
+    ))
+}
+
+/// Parse a `@HH:MM` clock time and return the `Duration` between now and the next time it
+/// occurs, rolling over to tomorrow if that time has already passed today.
+fn duration_until_clock_time(clock: &str) -> Result<Duration, ParseError> {
+    let invalid = || ParseError(format!("`@{}` isn't a valid clock time, expected `@HH:MM`", clock));
+
+    let re = Regex::new(r"^(?P<hour>\d{1,2}):(?P<minute>\d{2})$").unwrap();
+    let captures = re.captures(clock).ok_or_else(invalid)?;
+    let hour: u32 = captures["hour"].parse().map_err(|_| invalid())?;
+    let minute: u32 = captures["minute"].parse().map_err(|_| invalid())?;
+    if hour > 23 || minute > 59 {
+        return Err(invalid());
+    }
+
+    let now = chrono::Local::now();
+    let mut target = now.date().and_hms(hour, minute, 0);
+    if target <= now {
+        target = target + chrono::Duration::days(1);
     }
-    Duration::from_secs(seconds)
+
+    Ok((target - now).to_std().unwrap())
 }
 
 fn string_from_duration(duration: Duration) -> String {
@@ -57,13 +105,38 @@ fn string_from_duration(duration: Duration) -> String {
     }
 }
 
-fn large_text(text: &str) -> String {
-    let output = Command::new("figlet").arg(text).output().unwrap().stdout;
-    let output = String::from_utf8(output).unwrap();
-    output
+/// Which renderer draws the oversized countdown digits.
+#[derive(Clone)]
+enum Font {
+    /// The built-in bitmap font in the `bigtext` module.
+    Block,
+    /// Shell out to the `figlet` binary, as `act` used to do unconditionally.
+    Figlet,
 }
 
-fn run(countdown: Duration) -> bool {
+fn large_text(text: &str, font: &Font) -> String {
+    match font {
+        Font::Block => bigtext::render(text),
+        Font::Figlet => {
+            let output = Command::new("figlet").arg(text).output().unwrap().stdout;
+            String::from_utf8(output).unwrap()
+        }
+    }
+}
+
+/// The direction a clock should run: counting a fixed duration down to zero, or counting
+/// elapsed time up from zero, optionally stopping once it reaches a bound.
+#[derive(Clone, Copy)]
+enum Mode {
+    Countdown(Duration),
+    Countup(Option<Duration>),
+}
+
+/// Run a single countdown/stopwatch screen until it expires or the user quits. `header`, if
+/// given, is drawn on its own line above the big text — used by `--pomodoro` to show the
+/// current phase name and cycle counter. Returns `true` if the clock ran to completion, `false`
+/// if the user aborted it.
+fn run(mode: Mode, show_bar: bool, font: &Font, header: Option<&str>) -> bool {
     // To ensure the console is returned back into its normal state after we're done, we
     // instantiate this `ConsoleReset` object which resets the console when dropped. This way, the
     // console will always be reset, even if we forget to do it manually or we panic while
@@ -85,12 +158,26 @@ fn run(countdown: Duration) -> bool {
         }
         dt = Instant::now();
 
-        if countdown >= elapsed {
+        let displayed = match mode {
+            Mode::Countdown(countdown) => {
+                if countdown >= elapsed {
+                    Some(countdown - elapsed)
+                } else {
+                    None
+                }
+            }
+            Mode::Countup(bound) => match bound {
+                Some(bound) if elapsed >= bound => None,
+                _ => Some(elapsed),
+            },
+        };
+
+        if let Some(displayed) = displayed {
             let (window_width, window_height) = termion::terminal_size().unwrap();
 
-            let mut remaining = string_from_duration(countdown - elapsed);
+            let mut remaining = string_from_duration(displayed);
             if is_text_large {
-                remaining = large_text(&remaining);
+                remaining = large_text(&remaining, font);
             }
             let remaining_lines: Vec<&str> = remaining.split('\n').collect();
             let remaining_width = remaining_lines.iter().fold(0, |acc, line| acc.max(line.len()));
@@ -99,6 +186,20 @@ fn run(countdown: Duration) -> bool {
             if paused {
                 write!(stdout, "{}", termion::color::Fg(termion::color::Green));
             }
+            if let Some(header) = header {
+                let column = (window_width / 2)
+                    .saturating_sub(header.len() as u16 / 2)
+                    .max(1);
+                write!(
+                    stdout,
+                    "{}{}",
+                    termion::cursor::Goto(
+                        column,
+                        window_height / 2 - remaining_lines.len() as u16 / 2 - 2,
+                    ),
+                    header,
+                );
+            }
             for (i, line) in remaining_lines.iter().enumerate() {
                 write!(
                     stdout,
@@ -110,6 +211,32 @@ fn run(countdown: Duration) -> bool {
                     line,
                 );
             }
+            if show_bar {
+                if let Some(total) = match mode {
+                    Mode::Countdown(countdown) => Some(countdown),
+                    Mode::Countup(bound) => bound,
+                } {
+                    let fraction = elapsed.as_secs_f64() / total.as_secs_f64();
+                    let filled = ((window_width as f64 * fraction).round() as u16)
+                        .min(window_width);
+                    let bar: String = std::iter::repeat('\u{2588}')
+                        .take(filled as usize)
+                        .chain(std::iter::repeat('\u{2591}').take((window_width - filled) as usize))
+                        .collect();
+                    write!(
+                        stdout,
+                        "{}{}",
+                        termion::cursor::Goto(
+                            1,
+                            window_height / 2 - remaining_lines.len() as u16 / 2
+                                + remaining_lines.len() as u16
+                                + 1,
+                        ),
+                        bar,
+                    );
+                }
+            }
+
             write!(stdout, "{}", termion::color::Fg(termion::color::Reset));
             stdout.flush().unwrap();
         } else {
@@ -147,26 +274,146 @@ fn cli() -> App<'static, 'static> {
         .setting(clap::AppSettings::UnifiedHelpMessage)
         .max_term_width(80)
         .arg(clap::Arg::with_name("DURATION")
-             .help("Some span of time to countdown from, given as any combination of `1d`, `1h`, `1m`, or `1s`.\n\nExamples:\n\n> act 3d4h\n> act 1m30s\n> act '10d 3h 21m 10s'")
-             .required(true))
+             .help("Some span of time to countdown from, given as any combination of `1d`, `1h`, `1m`/`1min`, or `1s`. Alternatively, an absolute time of day to count down to, given as `@HH:MM`.\n\nIn `--up` mode this instead bounds how long the stopwatch runs before stopping; omit it to count up indefinitely.\n\nExamples:\n\n> act 3d4h\n> act 1m30s\n> act '10d 3h 21m 10s'\n> act @15:30")
+             .required_unless_one(&["up", "pomodoro"]))
+        .arg(clap::Arg::with_name("up")
+             .long("up")
+             .alias("count-up")
+             .help("Count up from zero instead of counting down, optionally bounded by DURATION"))
         .arg(clap::Arg::with_name("quiet")
              .short("q")
              .long("quiet")
              .help("Don't flash the console when the timer expires"))
+        .arg(clap::Arg::with_name("bar")
+             .long("bar")
+             .help("Draw a progress bar beneath the countdown showing elapsed vs. total time"))
+        .arg(clap::Arg::with_name("font")
+             .long("font")
+             .takes_value(true)
+             .possible_values(&["block", "figlet"])
+             .default_value("block")
+             .help("Renderer for the big digits. `block` is a bundled bitmap font with no external dependencies; `figlet` shells out to the `figlet` binary for the old look"))
+        .arg(clap::Arg::with_name("notify")
+             .long("notify")
+             .help("Fire a desktop notification when the timer expires"))
+        .arg(clap::Arg::with_name("notify-title")
+             .long("notify-title")
+             .takes_value(true)
+             .default_value("act")
+             .help("Title of the desktop notification fired by --notify"))
+        .arg(clap::Arg::with_name("notify-message")
+             .long("notify-message")
+             .takes_value(true)
+             .default_value("Time's up!")
+             .help("Body text of the desktop notification fired by --notify"))
+        .arg(clap::Arg::with_name("sound")
+             .long("sound")
+             .takes_value(true)
+             .value_name("FILE")
+             .help("Play this audio file instead of the default tone when the timer expires"))
+        .arg(clap::Arg::with_name("beeps")
+             .long("beeps")
+             .takes_value(true)
+             .default_value("3")
+             .validator(|v| v.parse::<u32>().map(|_| ()).map_err(|e| e.to_string()))
+             .help("How many times to repeat the alarm sound"))
+        .arg(clap::Arg::with_name("freq")
+             .long("freq")
+             .takes_value(true)
+             .default_value("880")
+             .validator(|v| v.parse::<f32>().map(|_| ()).map_err(|e| e.to_string()))
+             .help("Frequency in Hz of the default alarm tone"))
+        .arg(clap::Arg::with_name("beep-duration")
+             .long("beep-duration")
+             .takes_value(true)
+             .default_value("300")
+             .validator(|v| v.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+             .help("Length in milliseconds of each repetition of the default alarm tone"))
+        .arg(clap::Arg::with_name("pomodoro")
+             .long("pomodoro")
+             .help("Run a Pomodoro cycle: alternate --work and --pause timers, with a --long-break every 4th work interval, until aborted"))
+        .arg(clap::Arg::with_name("work")
+             .long("work")
+             .takes_value(true)
+             .default_value("25m")
+             .help("Length of each work interval in --pomodoro mode"))
+        .arg(clap::Arg::with_name("pause")
+             .long("pause")
+             .takes_value(true)
+             .default_value("5m")
+             .help("Length of each short break in --pomodoro mode"))
+        .arg(clap::Arg::with_name("long-break")
+             .long("long-break")
+             .takes_value(true)
+             .default_value("15m")
+             .help("Length of the long break taken after every 4th work interval in --pomodoro mode"))
+}
+
+/// Parse a `DURATION`-shaped argument, exiting with a friendly message instead of panicking if
+/// it's malformed.
+fn parse_duration_or_exit(string: &str) -> Duration {
+    match duration_from_string(string) {
+        Ok(duration) => duration,
+        Err(error) => {
+            eprintln!("act: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn build_alert(args: &clap::ArgMatches) -> alert::Alert {
+    alert::Alert {
+        notify: args.is_present("notify"),
+        title: args.value_of("notify-title").unwrap().to_string(),
+        message: args.value_of("notify-message").unwrap().to_string(),
+        sound: args.value_of("sound").map(std::path::PathBuf::from),
+        beeps: args.value_of("beeps").unwrap().parse().unwrap(),
+        freq: args.value_of("freq").unwrap().parse().unwrap(),
+        beep_duration: Duration::from_millis(args.value_of("beep-duration").unwrap().parse().unwrap()),
+    }
 }
 
 fn main() {
     let args = cli().get_matches();
-    
-    // Get the countdown duration, passed in as an app argument. Then, add 1 second so that the
-    // amount of time set to countdown from is what's actually shown when the app starts.
-    let countdown =
-        duration_from_string(args.value_of("DURATION").unwrap()) + Duration::from_secs(1);
-    let is_quiet = args.is_present("quiet"); 
-
-    let finished = run(countdown);
+
+    let is_quiet = args.is_present("quiet");
+    let show_bar = args.is_present("bar");
+    let font = match args.value_of("font").unwrap() {
+        "figlet" => Font::Figlet,
+        _ => Font::Block,
+    };
+    let alert = build_alert(&args);
+
+    if args.is_present("pomodoro") {
+        let settings = pomodoro::Settings {
+            work: parse_duration_or_exit(args.value_of("work").unwrap()),
+            pause: parse_duration_or_exit(args.value_of("pause").unwrap()),
+            long_break: parse_duration_or_exit(args.value_of("long-break").unwrap()),
+            show_bar,
+            font,
+            is_quiet,
+            alert,
+        };
+        pomodoro::run(&settings);
+        return;
+    }
+
+    // Get the countdown/bound duration, passed in as an app argument.
+    let duration = args.value_of("DURATION").map(parse_duration_or_exit);
+
+    let mode = if args.is_present("up") {
+        // No display bias here: the bound is how long the stopwatch actually runs before
+        // stopping/alerting, not a countdown readout that needs rounding up to its first frame.
+        Mode::Countup(duration)
+    } else {
+        // Add 1 second so that the amount of time set to countdown from is what's actually shown
+        // when the countdown starts (`string_from_duration` floors to whole seconds).
+        Mode::Countdown(duration.unwrap() + Duration::from_secs(1))
+    };
+
+    let finished = run(mode, show_bar, &font, None);
     if finished && !is_quiet {
-        print!("{}", BELL);
+        alert.fire();
     }
 }
 
@@ -174,31 +421,71 @@ fn main() {
 ///
 /// Typically causes the terminal emulator to play a sound and/or flash
 /// the window. On i3, it'll even mark the workspace playing the `BEL` as urgent.
-const BELL: &str = "\x07";
+pub(crate) const BELL: &str = "\x07";
 
 #[test]
 fn parse_seconds() {
-    assert_eq!(duration_from_string("30s"), Duration::from_secs(30));
+    assert_eq!(duration_from_string("30s").unwrap(), Duration::from_secs(30));
 }
 
 #[test]
 fn parse_minutes() {
-    assert_eq!(duration_from_string("35m"), Duration::from_secs(35 * 60));
+    assert_eq!(
+        duration_from_string("35m").unwrap(),
+        Duration::from_secs(35 * 60)
+    );
 }
 
 #[test]
 fn parse_hours() {
-    assert_eq!(duration_from_string("3h"), Duration::from_secs(3 * 60 * 60));
+    assert_eq!(
+        duration_from_string("3h").unwrap(),
+        Duration::from_secs(3 * 60 * 60)
+    );
+}
+
+#[test]
+fn parse_days() {
+    assert_eq!(
+        duration_from_string("2d").unwrap(),
+        Duration::from_secs(2 * 60 * 60 * 24)
+    );
 }
 
 #[test]
 fn parse_seconds_and_minutes_and_hours() {
     assert_eq!(
-        duration_from_string("25m100s"),
+        duration_from_string("25m100s").unwrap(),
         Duration::from_secs(25 * 60 + 100)
     );
     assert_eq!(
-        duration_from_string("1h1h1h"),
-        Duration::from_secs(3 * 60 * 60)
+        duration_from_string("1h30m").unwrap(),
+        Duration::from_secs(60 * 60 + 30 * 60)
+    );
+}
+
+#[test]
+fn parse_min_alias_for_minutes() {
+    assert_eq!(
+        duration_from_string("5min").unwrap(),
+        Duration::from_secs(5 * 60)
+    );
+}
+
+#[test]
+fn parse_tolerates_stray_whitespace() {
+    assert_eq!(
+        duration_from_string(" 1h 30m ").unwrap(),
+        Duration::from_secs(60 * 60 + 30 * 60)
     );
 }
+
+#[test]
+fn parse_empty_string_is_an_error() {
+    assert!(duration_from_string("").is_err());
+}
+
+#[test]
+fn parse_garbage_is_an_error() {
+    assert!(duration_from_string("not a duration").is_err());
+}
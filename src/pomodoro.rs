@@ -0,0 +1,62 @@
+//! `--pomodoro` mode: chains `run()` calls into alternating work and break phases following the
+//! classic Pomodoro technique, showing the phase name and cycle counter above the big text and
+//! firing the alert between phases.
+
+use std::time::Duration;
+
+use crate::alert::Alert;
+use crate::{Font, Mode};
+
+/// How many work intervals make up a full cycle before a long break.
+const WORK_INTERVALS_PER_CYCLE: u32 = 4;
+
+pub struct Settings {
+    pub work: Duration,
+    pub pause: Duration,
+    pub long_break: Duration,
+    pub show_bar: bool,
+    pub font: Font,
+    pub is_quiet: bool,
+    pub alert: Alert,
+}
+
+/// Run work/break phases back to back until the user aborts one with Esc/Ctrl-C.
+pub fn run(settings: &Settings) {
+    let mut interval: u32 = 1;
+    loop {
+        let cycle = (interval - 1) / WORK_INTERVALS_PER_CYCLE + 1;
+
+        let header = format!("Work #{} (cycle {})", interval, cycle);
+        if !run_phase(settings, settings.work, &header) {
+            return;
+        }
+        fire_alert(settings);
+
+        let (header, duration) = if interval % WORK_INTERVALS_PER_CYCLE == 0 {
+            (format!("Long break (cycle {})", cycle), settings.long_break)
+        } else {
+            (format!("Break #{} (cycle {})", interval, cycle), settings.pause)
+        };
+        if !run_phase(settings, duration, &header) {
+            return;
+        }
+        fire_alert(settings);
+
+        interval += 1;
+    }
+}
+
+fn run_phase(settings: &Settings, duration: Duration, header: &str) -> bool {
+    crate::run(
+        Mode::Countdown(duration + Duration::from_secs(1)),
+        settings.show_bar,
+        &settings.font,
+        Some(header),
+    )
+}
+
+fn fire_alert(settings: &Settings) {
+    if !settings.is_quiet {
+        settings.alert.fire();
+    }
+}